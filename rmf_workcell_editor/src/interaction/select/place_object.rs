@@ -15,12 +15,18 @@
  *
 */
 
+use crate::interaction::select::placement_preview::PlacementPreviewPlugin;
+use crate::interaction::select::primitive_mesh::primitive_mesh;
 use crate::interaction::select::{place_object_3d::*, replace_parent_3d::*};
-use crate::{interaction::*, CurrentWorkspace};
+use crate::workcell::fuel::FuelClient;
+use crate::{interaction::*, CurrentWorkspace, VisualMeshMarker};
 use bevy::ecs::system::{Command, SystemParam, SystemState};
 use bevy::prelude::*;
+use bevy::tasks::{futures_lite::future, IoTaskPool, Task};
 use bevy_impulse::*;
-use rmf_workcell_format::Model;
+use rmf_workcell_format::{AssetSource, Model, PrimitiveShape};
+
+use crate::workcell::fuel::FuelError;
 
 #[derive(Default)]
 pub struct ObjectPlacementPlugin {}
@@ -29,6 +35,80 @@ impl Plugin for ObjectPlacementPlugin {
     fn build(&self, app: &mut App) {
         let services = ObjectPlacementServices::from_app(app);
         app.insert_resource(services);
+        app.init_resource::<FuelClient>();
+        app.add_plugins(PlacementPreviewPlugin);
+        app.add_systems(
+            Update,
+            (generate_placed_primitive_geometry, poll_fuel_resolution),
+        );
+    }
+}
+
+/// Spawned while a Fuel model is being downloaded/cached in the background,
+/// so [`poll_fuel_resolution`] knows to pick the result back up and forward
+/// it into the placement selector once it lands.
+#[derive(Component)]
+struct PendingFuelPlacement {
+    owner: String,
+    name: String,
+    version: u32,
+    task: Task<Result<AssetSource, FuelError>>,
+}
+
+/// Drain [`PendingFuelPlacement`] tasks as they complete: remember the
+/// resolved [`AssetSource`] in [`FuelClient`] and hand the model off to the
+/// placement selector, the same way an already-cached model would be placed
+/// immediately. Polling here (rather than blocking on the task) is what lets
+/// `ObjectPlacement::place_fuel_model_3d` kick off a download without ever
+/// stalling the app's schedule.
+fn poll_fuel_resolution(
+    mut commands: Commands,
+    mut pending: Query<(Entity, &mut PendingFuelPlacement)>,
+    mut placement: ObjectPlacement,
+) {
+    for (entity, mut job) in &mut pending {
+        let Some(result) = future::block_on(future::poll_once(&mut job.task)) else {
+            continue;
+        };
+        commands.entity(entity).despawn();
+        match result {
+            Ok(source) => {
+                placement
+                    .fuel_client
+                    .remember_resolved(&job.owner, &job.name, job.version, source);
+                placement.place_object_3d(PlaceableObject::FuelModel {
+                    owner: job.owner.clone(),
+                    name: job.name.clone(),
+                    version: job.version,
+                });
+            }
+            Err(err) => {
+                error!(
+                    "Failed to resolve Fuel model {}/{}: {err}",
+                    job.owner, job.name
+                );
+            }
+        }
+    }
+}
+
+/// A committed [`PlaceableObject::Primitive`] only carries a `PrimitiveShape`
+/// component (that's what `generate_workcell` serializes it through), not a
+/// mesh to render or interact with. Generate one here and tag the entity as
+/// a `VisualMeshMarker`, the same role a placed `Model` gets, so the shape
+/// is both visible and selectable once placed.
+fn generate_placed_primitive_geometry(
+    mut commands: Commands,
+    new_primitives: Query<(Entity, &PrimitiveShape), (Added<PrimitiveShape>, Without<Handle<Mesh>>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (e, shape) in &new_primitives {
+        commands.entity(e).insert((
+            meshes.add(primitive_mesh(shape)),
+            materials.add(StandardMaterial::default()),
+            VisualMeshMarker,
+        ));
     }
 }
 
@@ -62,6 +142,7 @@ pub struct ObjectPlacement<'w, 's> {
     pub commands: Commands<'w, 's>,
     current_workspace: Res<'w, CurrentWorkspace>,
     current_selection: Res<'w, Selection>,
+    fuel_client: ResMut<'w, FuelClient>,
 }
 
 impl<'w, 's> ObjectPlacement<'w, 's> {
@@ -84,6 +165,45 @@ impl<'w, 's> ObjectPlacement<'w, 's> {
         });
     }
 
+    /// Place a model resolved from the Gazebo Fuel catalog. If it isn't
+    /// already cached, kicks off a background [`IoTaskPool`] task to
+    /// download and cache it through [`FuelClient`] and defers the actual
+    /// placement until `poll_fuel_resolution` picks the result back up --
+    /// network I/O and zip extraction are too slow to run on the app's own
+    /// schedule without freezing the UI.
+    pub fn place_fuel_model_3d(&mut self, owner: String, name: String, version: u32) {
+        if self.fuel_client.cached(&owner, &name, version).is_some() {
+            self.place_object_3d(PlaceableObject::FuelModel {
+                owner,
+                name,
+                version,
+            });
+            return;
+        }
+
+        let cache_dir = self.fuel_client.cache_dir();
+        let task = {
+            let owner = owner.clone();
+            let name = name.clone();
+            IoTaskPool::get()
+                .spawn(async move { FuelClient::fetch(owner, name, version, cache_dir) })
+        };
+        self.commands.spawn(PendingFuelPlacement {
+            owner,
+            name,
+            version,
+            task,
+        });
+    }
+
+    /// Place a parameterized primitive shape (box, cylinder, sphere or
+    /// capsule) instead of a mesh-backed model, so users can drop basic
+    /// geometry straight from a palette without hand-authoring an
+    /// `AssetSource`.
+    pub fn place_primitive_3d(&mut self, shape: PrimitiveShape) {
+        self.place_object_3d(PlaceableObject::Primitive(shape));
+    }
+
     pub fn replace_parent_3d(&mut self, object: Entity, workspace: Entity) {
         let state = self
             .commands
@@ -105,12 +225,22 @@ impl<'w, 's> ObjectPlacement<'w, 's> {
 /// Trait to be implemented to allow placing models with commands
 pub trait ObjectPlacementExt<'w, 's> {
     fn place_model_3d(&mut self, object: Model);
+    fn place_fuel_model_3d(&mut self, owner: String, name: String, version: u32);
+    fn place_primitive_3d(&mut self, shape: PrimitiveShape);
 }
 
 impl<'w, 's> ObjectPlacementExt<'w, 's> for Commands<'w, 's> {
     fn place_model_3d(&mut self, object: Model) {
         self.add(ObjectPlaceCommand(object));
     }
+
+    fn place_fuel_model_3d(&mut self, owner: String, name: String, version: u32) {
+        self.add(FuelModelPlaceCommand { owner, name, version });
+    }
+
+    fn place_primitive_3d(&mut self, shape: PrimitiveShape) {
+        self.add(PrimitivePlaceCommand(shape));
+    }
 }
 
 #[derive(Deref, DerefMut)]
@@ -124,3 +254,30 @@ impl Command for ObjectPlaceCommand {
         system_state.apply(world);
     }
 }
+
+pub struct FuelModelPlaceCommand {
+    pub owner: String,
+    pub name: String,
+    pub version: u32,
+}
+
+impl Command for FuelModelPlaceCommand {
+    fn apply(self, world: &mut World) {
+        let mut system_state: SystemState<ObjectPlacement> = SystemState::new(world);
+        let mut placement = system_state.get_mut(world);
+        placement.place_fuel_model_3d(self.owner, self.name, self.version);
+        system_state.apply(world);
+    }
+}
+
+#[derive(Deref, DerefMut)]
+pub struct PrimitivePlaceCommand(PrimitiveShape);
+
+impl Command for PrimitivePlaceCommand {
+    fn apply(self, world: &mut World) {
+        let mut system_state: SystemState<ObjectPlacement> = SystemState::new(world);
+        let mut placement = system_state.get_mut(world);
+        placement.place_primitive_3d(self.0);
+        system_state.apply(world);
+    }
+}
@@ -0,0 +1,122 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::workcell::fuel::FuelClient;
+use crate::{interaction::*, VisualMeshMarker};
+use bevy::prelude::*;
+use bevy_impulse::*;
+use rmf_workcell_format::{Model, NameInWorkcell, Pose, PrimitiveShape};
+
+/// The object a [`PlaceObject3d`] selector will spawn once the user
+/// confirms a placement.
+#[derive(Debug, Clone)]
+pub enum PlaceableObject {
+    /// A locally-sourced mesh model.
+    Model(Model),
+    /// A model resolved from the Gazebo Fuel catalog. By the time a
+    /// selector carrying this variant runs, `owner`/`name`/`version` are
+    /// expected to already be resolved in [`FuelClient`] (see
+    /// `ObjectPlacement::place_fuel_model_3d`), so the spawned entity can
+    /// look its cached [`rmf_workcell_format::AssetSource`] straight back up.
+    FuelModel {
+        owner: String,
+        name: String,
+        version: u32,
+    },
+    /// A parameterized primitive shape.
+    Primitive(PrimitiveShape),
+}
+
+/// Input for the object placement selector: what to place, where to parent
+/// it, and which workcell it belongs to.
+#[derive(Component, Clone)]
+pub struct PlaceObject3d {
+    pub object: PlaceableObject,
+    pub parent: Option<Entity>,
+    pub workspace: Entity,
+}
+
+/// Selection filter used by the placement hover service. Placement doesn't
+/// restrict which entity the user is allowed to hover over (anything can be
+/// a placement reference), so this just passes every candidate through.
+#[derive(Default)]
+pub struct PlaceObject3dFilter;
+
+pub fn spawn_place_object_3d_workflow(
+    _hover_service_object_3d: Service<(), (), Hover>,
+    app: &mut App,
+) -> Service<Option<Entity>, ()> {
+    app.world
+        .spawn_service(commit_place_object_3d.into_blocking_service())
+}
+
+/// Commit the [`PlaceObject3d`] carried by the selector's state entity:
+/// spawn the real entity for whichever [`PlaceableObject`] variant it holds,
+/// parented under `place.parent` (or the workcell root if there's no
+/// current selection), and clean up the state entity afterwards.
+fn commit_place_object_3d(
+    In(state): In<Option<Entity>>,
+    mut commands: Commands,
+    q_place: Query<&SelectorInput<PlaceObject3d>>,
+    fuel_client: Res<FuelClient>,
+) {
+    let Some(state) = state else {
+        return;
+    };
+    let Ok(SelectorInput(place)) = q_place.get(state) else {
+        return;
+    };
+
+    let mut new_entity = commands.spawn((default_name_for(&place.object), Pose::default()));
+    new_entity.set_parent(place.parent.unwrap_or(place.workspace));
+
+    match &place.object {
+        PlaceableObject::Model(model) => {
+            new_entity.insert((model.source.clone(), VisualMeshMarker));
+        }
+        PlaceableObject::FuelModel {
+            owner,
+            name,
+            version,
+        } => {
+            if let Some(source) = fuel_client.cached(owner, name, *version) {
+                new_entity.insert((source, VisualMeshMarker));
+            } else {
+                warn!(
+                    "Fuel model {owner}/{name} was not resolved before placement; \
+                     spawning it without geometry"
+                );
+            }
+        }
+        PlaceableObject::Primitive(shape) => {
+            new_entity.insert((shape.clone(), VisualMeshMarker));
+        }
+    }
+
+    commands.entity(state).despawn();
+}
+
+fn default_name_for(object: &PlaceableObject) -> NameInWorkcell {
+    NameInWorkcell(
+        match object {
+            PlaceableObject::Model(_) => "model",
+            PlaceableObject::FuelModel { name, .. } => name.as_str(),
+            PlaceableObject::Primitive(_) => "primitive",
+        }
+        .to_string(),
+    )
+}
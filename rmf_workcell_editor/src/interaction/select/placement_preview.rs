@@ -0,0 +1,205 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::interaction::select::place_object_3d::*;
+use crate::interaction::select::primitive_mesh::primitive_mesh;
+use crate::interaction::{Hover, SelectorInput};
+use crate::workcell::fuel::FuelClient;
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy::scene::{SceneInstance, SceneSpawner};
+
+/// Render layer that placement previews are drawn on. Kept off the default
+/// layer (0) so a ghost never shows up in a normal camera view, and only the
+/// placement camera opts into it.
+pub const MODEL_PREVIEW_LAYER: u8 = 6;
+
+/// Marks the semi-transparent ghost entity spawned while an object
+/// placement selector is active.
+#[derive(Component)]
+pub struct PlacementPreview;
+
+#[derive(Default)]
+pub struct PlacementPreviewPlugin {}
+
+impl Plugin for PlacementPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_placement_preview,
+                update_placement_preview,
+                ghost_preview_scene_materials,
+                despawn_placement_preview,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Marks a ghost preview's scene instance once [`ghost_preview_scene_materials`]
+/// has already swapped its materials for translucent copies, so it isn't
+/// redone (and the materials it already replaced re-replaced) every frame.
+#[derive(Component)]
+struct GhostMaterialApplied;
+
+/// Spawn a ghost of the object being placed as soon as a [`PlaceObject3d`]
+/// selector starts, before the user has hovered over anything to confirm.
+/// The ghost carries the real mesh (loaded the same way a committed model
+/// would be, or generated on the fly for a primitive) so the user sees
+/// accurate geometry rather than a placeholder bounding box.
+fn spawn_placement_preview(
+    mut commands: Commands,
+    new_selectors: Query<
+        (Entity, &SelectorInput<PlaceObject3d>),
+        Added<SelectorInput<PlaceObject3d>>,
+    >,
+    existing_previews: Query<(), With<PlacementPreview>>,
+    asset_server: Res<AssetServer>,
+    fuel_client: Res<FuelClient>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !existing_previews.is_empty() {
+        return;
+    }
+    for (_, SelectorInput(place)) in &new_selectors {
+        let mut preview = commands.spawn((
+            PlacementPreview,
+            RenderLayers::layer(MODEL_PREVIEW_LAYER.into()),
+        ));
+
+        match &place.object {
+            PlaceableObject::Model(model) => {
+                let scene = asset_server.load(format!("{}#Scene0", model.source));
+                preview.insert(SceneBundle {
+                    scene,
+                    visibility: Visibility::Visible,
+                    ..default()
+                });
+            }
+            PlaceableObject::Primitive(shape) => {
+                let ghost_material = materials.add(StandardMaterial {
+                    base_color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                });
+                preview.insert(PbrBundle {
+                    mesh: meshes.add(primitive_mesh(shape)),
+                    material: ghost_material,
+                    visibility: Visibility::Visible,
+                    ..default()
+                });
+            }
+            PlaceableObject::FuelModel {
+                owner,
+                name,
+                version,
+            } => {
+                // By the time this selector is running, place_fuel_model_3d
+                // has already resolved the model (that's a precondition of
+                // starting the selector at all), so the cache lookup here
+                // should always hit; the hidden fallback only matters if
+                // that invariant is ever violated.
+                match fuel_client.cached(owner, name, *version) {
+                    Some(source) => {
+                        let scene = asset_server.load(format!("{source}#Scene0"));
+                        preview.insert(SceneBundle {
+                            scene,
+                            visibility: Visibility::Visible,
+                            ..default()
+                        });
+                    }
+                    None => {
+                        preview.insert(SpatialBundle::HIDDEN_IDENTITY);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Every frame, move the ghost to wherever the placement hover service says
+/// the object would land if committed right now.
+fn update_placement_preview(
+    hover: Res<Hover>,
+    q_transforms: Query<&GlobalTransform>,
+    mut q_preview: Query<&mut Transform, With<PlacementPreview>>,
+) {
+    let Some(target) = hover.0 else {
+        return;
+    };
+    let Ok(target_transform) = q_transforms.get(target) else {
+        return;
+    };
+    for mut transform in &mut q_preview {
+        *transform = target_transform.compute_transform();
+    }
+}
+
+/// A scene-backed ghost (`Model`/`FuelModel`) is loaded with the asset's own
+/// opaque materials -- glTF/SDF scenes spawn asynchronously, so there's
+/// nothing to override at the time `spawn_placement_preview` runs. Once the
+/// scene has actually finished spawning, swap every material its meshes use
+/// for a translucent copy so every kind of ghost reads as a preview, not
+/// just the generated-mesh primitives.
+fn ghost_preview_scene_materials(
+    mut commands: Commands,
+    scene_spawner: Res<SceneSpawner>,
+    q_previews: Query<
+        (Entity, &SceneInstance),
+        (With<PlacementPreview>, Without<GhostMaterialApplied>),
+    >,
+    q_children: Query<&Children>,
+    q_material_handles: Query<&Handle<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (preview, instance) in &q_previews {
+        if !scene_spawner.instance_is_ready(**instance) {
+            continue;
+        }
+        for descendant in q_children.iter_descendants(preview) {
+            let Ok(handle) = q_material_handles.get(descendant) else {
+                continue;
+            };
+            let Some(material) = materials.get(handle) else {
+                continue;
+            };
+            let mut ghost = material.clone();
+            ghost.base_color.set_a(0.5);
+            ghost.alpha_mode = AlphaMode::Blend;
+            let ghost_handle = materials.add(ghost);
+            commands.entity(descendant).insert(ghost_handle);
+        }
+        commands.entity(preview).insert(GhostMaterialApplied);
+    }
+}
+
+/// Remove the ghost once its selector has finished (either committed or
+/// cancelled), so a stray preview never lingers after placement ends.
+fn despawn_placement_preview(
+    mut commands: Commands,
+    q_preview: Query<Entity, With<PlacementPreview>>,
+    active_selectors: Query<(), With<SelectorInput<PlaceObject3d>>>,
+) {
+    if active_selectors.is_empty() {
+        for preview in &q_preview {
+            commands.entity(preview).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use bevy::prelude::*;
+use rmf_workcell_format::PrimitiveShape;
+
+/// Build a renderable mesh for a [`PrimitiveShape`], shared by the
+/// placement ghost preview and the mesh generated for a committed
+/// primitive placement.
+pub fn primitive_mesh(shape: &PrimitiveShape) -> Mesh {
+    match shape {
+        PrimitiveShape::Box { size } => Mesh::from(shape::Box::new(size[0], size[1], size[2])),
+        PrimitiveShape::Cylinder { radius, length } => Mesh::from(shape::Cylinder {
+            radius: *radius,
+            height: *length,
+            ..default()
+        }),
+        PrimitiveShape::Sphere { radius } => Mesh::from(shape::UVSphere {
+            radius: *radius,
+            ..default()
+        }),
+        PrimitiveShape::Capsule { radius, length } => Mesh::from(shape::Capsule {
+            radius: *radius,
+            depth: *length,
+            ..default()
+        }),
+    }
+}
@@ -0,0 +1,267 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rmf_workcell_format::{AssetSource, Geometry, Workcell};
+use thiserror::Error as ThisError;
+
+#[derive(ThisError, Debug)]
+pub enum AssetPathError {
+    #[error("failed to read source mesh {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("failed to copy mesh into package: {0}")]
+    Copy(std::io::Error),
+    #[error("could not compute a relative path from {0} to {1}")]
+    NotRelative(PathBuf, PathBuf),
+}
+
+/// URI convention to rewrite a relativized mesh reference into. Different
+/// description formats resolve package-relative paths differently, so the
+/// same copied-and-deduplicated mesh file needs a different `<uri>`
+/// depending on which exporter is writing it out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeUriStyle {
+    /// `package://<package_name>/<relative path>`, resolved by ROS tooling
+    /// (`urdf_package_exporter`'s output).
+    RosPackage,
+    /// A path relative to the directory the description file is written
+    /// into, resolved directly by Gazebo-style SDF loaders without needing
+    /// `GZ_SIM_RESOURCE_PATH` to be configured with anything beyond that
+    /// directory (`sdf_package_exporter`'s output).
+    ModelRelative,
+}
+
+/// Copies every mesh referenced by a local [`AssetSource`] into a `meshes/`
+/// subfolder of the exported package and rewrites the source to a
+/// reference in the requested [`RelativeUriStyle`], so packages don't leak
+/// the author's absolute local filesystem layout. Remote and Fuel sources
+/// are passed through untouched since they're already portable.
+pub struct AssetRelativizer<'a> {
+    /// Root of the package being generated, e.g. the directory a URDF or
+    /// SDF file is written into.
+    output_directory: &'a Path,
+    /// Maps a content hash to the mesh path already copied for it, so two
+    /// models that reference the same mesh (even under different original
+    /// paths) are only copied once and two *different* meshes that happen
+    /// to share a filename don't clobber each other.
+    copied: HashMap<u64, PathBuf>,
+}
+
+impl<'a> AssetRelativizer<'a> {
+    pub fn new(output_directory: &'a Path) -> Self {
+        Self {
+            output_directory,
+            copied: HashMap::new(),
+        }
+    }
+
+    /// Rewrite `source` relative to `package_name` in the requested `style`,
+    /// copying the underlying file into `<output_directory>/meshes/` the
+    /// first time it is seen.
+    pub fn relativize(
+        &mut self,
+        source: &AssetSource,
+        package_name: &str,
+        style: RelativeUriStyle,
+    ) -> Result<AssetSource, AssetPathError> {
+        let AssetSource::Local(path) = source else {
+            // Remote URLs and Fuel references are already portable.
+            return Ok(source.clone());
+        };
+
+        let bytes =
+            fs::read(path).map_err(|e| AssetPathError::Read(path.clone(), e))?;
+        let hash = content_hash(&bytes);
+
+        let meshes_dir = self.output_directory.join("meshes");
+        let dest_path = if let Some(existing) = self.copied.get(&hash) {
+            existing.clone()
+        } else {
+            fs::create_dir_all(&meshes_dir).map_err(AssetPathError::Copy)?;
+            let file_name = unique_file_name(&meshes_dir, path);
+            let dest = meshes_dir.join(&file_name);
+            fs::copy(path, &dest).map_err(AssetPathError::Copy)?;
+            self.copied.insert(hash, dest.clone());
+            dest
+        };
+
+        let relative = pathdiff::diff_paths(&dest_path, self.output_directory)
+            .ok_or_else(|| {
+                AssetPathError::NotRelative(dest_path.clone(), self.output_directory.to_path_buf())
+            })?;
+
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let uri = match style {
+            RelativeUriStyle::RosPackage => format!("package://{package_name}/{relative}"),
+            RelativeUriStyle::ModelRelative => relative,
+        };
+        Ok(AssetSource::Package(uri))
+    }
+}
+
+/// De-duplicate by appending a numeric suffix when the filename is already
+/// taken in `meshes_dir`. Within one [`AssetRelativizer`] identical content
+/// never reaches this point, but any file already present counts as taken,
+/// including one with the same content left by an earlier export.
+fn unique_file_name(meshes_dir: &Path, source: &Path) -> String {
+    let file_name = source
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mesh".to_string());
+    if !meshes_dir.join(&file_name).exists() {
+        return file_name;
+    }
+    let stem = source
+        .file_stem()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mesh".to_string());
+    let ext = source
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut counter = 1;
+    loop {
+        let candidate = format!("{stem}_{counter}{ext}");
+        if !meshes_dir.join(&candidate).exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+/// Rewrite every mesh-backed visual/collision `AssetSource` in `workcell` to
+/// a reference in the requested [`RelativeUriStyle`], copying the
+/// underlying files into `<output_directory>/meshes/`. Shared by the URDF
+/// and SDF exporters (each passing the URI style their own format expects)
+/// so neither leaks the author's absolute local filesystem layout.
+pub fn relativize_workcell_assets(
+    workcell: &mut Workcell,
+    output_directory: &Path,
+    package_name: &str,
+    style: RelativeUriStyle,
+) -> Result<(), AssetPathError> {
+    let mut relativizer = AssetRelativizer::new(output_directory);
+    for model in workcell
+        .visuals
+        .values_mut()
+        .chain(workcell.collisions.values_mut())
+    {
+        if let Geometry::Mesh { source, .. } = &mut model.bundle.geometry {
+            *source = relativizer.relativize(source, package_name, style)?;
+        }
+    }
+    Ok(())
+}
+
+fn content_hash(bytes: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rmf_workcell_asset_path_test_{name}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn unique_file_name_suffixes_on_collision() {
+        let meshes_dir = scratch_dir("unique_file_name");
+        fs::write(meshes_dir.join("mesh.stl"), b"a").unwrap();
+        assert_eq!(
+            unique_file_name(&meshes_dir, Path::new("/anywhere/mesh.stl")),
+            "mesh_1.stl"
+        );
+        assert_eq!(
+            unique_file_name(&meshes_dir, Path::new("/anywhere/other.stl")),
+            "other.stl"
+        );
+    }
+
+    #[test]
+    fn relativize_dedupes_identical_content() {
+        let src_dir = scratch_dir("relativize_src");
+        let out_dir = scratch_dir("relativize_out");
+        let mesh_a = src_dir.join("a.stl");
+        let mesh_b = src_dir.join("b.stl");
+        fs::write(&mesh_a, b"same bytes").unwrap();
+        fs::write(&mesh_b, b"same bytes").unwrap();
+
+        let mut relativizer = AssetRelativizer::new(&out_dir);
+        let first = relativizer
+            .relativize(
+                &AssetSource::Local(mesh_a),
+                "my_pkg",
+                RelativeUriStyle::RosPackage,
+            )
+            .unwrap();
+        let second = relativizer
+            .relativize(
+                &AssetSource::Local(mesh_b),
+                "my_pkg",
+                RelativeUriStyle::RosPackage,
+            )
+            .unwrap();
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(fs::read_dir(out_dir.join("meshes")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn relativize_respects_uri_style() {
+        let src_dir = scratch_dir("relativize_style_src");
+        let out_dir = scratch_dir("relativize_style_out");
+        let mesh = src_dir.join("a.stl");
+        fs::write(&mesh, b"bytes").unwrap();
+
+        let package = AssetRelativizer::new(&out_dir)
+            .relativize(
+                &AssetSource::Local(mesh.clone()),
+                "my_pkg",
+                RelativeUriStyle::RosPackage,
+            )
+            .unwrap();
+        assert!(package.to_string().starts_with("package://my_pkg/"));
+
+        let model_relative = AssetRelativizer::new(&out_dir)
+            .relativize(&AssetSource::Local(mesh), "my_pkg", RelativeUriStyle::ModelRelative)
+            .unwrap();
+        assert!(!model_relative.to_string().contains("package://"));
+    }
+
+    #[test]
+    fn relativize_passes_through_non_local_sources() {
+        let out_dir = scratch_dir("relativize_passthrough");
+        let remote = AssetSource::Remote("https://example.com/model.glb".to_string());
+        let result = AssetRelativizer::new(&out_dir)
+            .relativize(&remote, "my_pkg", RelativeUriStyle::RosPackage)
+            .unwrap();
+        assert_eq!(result.to_string(), remote.to_string());
+    }
+}
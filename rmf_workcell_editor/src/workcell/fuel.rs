@@ -0,0 +1,173 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use bevy::prelude::*;
+use rmf_workcell_format::AssetSource;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use thiserror::Error as ThisError;
+
+/// Key a resolved model is cached under: owner, name and version together
+/// are the only thing that uniquely identifies a Fuel model.
+type FuelModelKey = (String, String, u32);
+
+/// One entry in a Fuel owner or collection listing. Enough information to
+/// render a browsable catalog before the user has committed to placing
+/// anything. Mirrors the subset of the Fuel server's JSON model listing
+/// response that this client actually needs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FuelModel {
+    pub owner: String,
+    pub name: String,
+    pub version: u32,
+    pub thumbnail_url: Option<String>,
+}
+
+#[derive(ThisError, Debug, Clone)]
+pub enum FuelError {
+    #[error("failed to reach the Fuel server: {0}")]
+    Request(String),
+    #[error("model {owner}/{name} was not found on the Fuel server")]
+    NotFound { owner: String, name: String },
+    #[error("failed to cache model {owner}/{name} locally: {reason}")]
+    Cache {
+        owner: String,
+        name: String,
+        reason: String,
+    },
+}
+
+/// Resource wrapping a client for the Gazebo Fuel model catalog
+/// (<https://app.gazebosim.org>). Lists models by owner or collection, and
+/// resolves a selection into a locally cached [`AssetSource`] so that a
+/// `Workcell` saved after placement only ever references a stable local
+/// path, never the remote catalog.
+///
+/// Resolving a model involves network I/O and a zip extraction, so this
+/// client never drives that work itself: [`FuelClient::fetch`] is a plain
+/// associated function that doesn't borrow `self`, so callers can hand its
+/// inputs off to a [`bevy::tasks::IoTaskPool`] task and poll it from a
+/// system instead of blocking the app on it (see
+/// `ObjectPlacement::place_fuel_model_3d`).
+#[derive(Resource)]
+pub struct FuelClient {
+    /// Directory that downloaded models are cached under, keyed by
+    /// `owner/name/version`.
+    cache_dir: PathBuf,
+    /// Models that have already been resolved this session, so repeatedly
+    /// placing the same model doesn't re-download it.
+    resolved: HashMap<FuelModelKey, AssetSource>,
+}
+
+impl FromWorld for FuelClient {
+    fn from_world(_world: &mut World) -> Self {
+        let cache_dir = dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("open-rmf")
+            .join("fuel");
+        FuelClient::new(cache_dir)
+    }
+}
+
+impl FuelClient {
+    pub fn new(cache_dir: PathBuf) -> Self {
+        Self {
+            cache_dir,
+            resolved: HashMap::new(),
+        }
+    }
+
+    /// List the models published under `owner`, optionally scoped to a
+    /// single `collection`. Like [`FuelClient::fetch`], this is plain
+    /// blocking I/O so it can be run from a background task rather than the
+    /// app's own schedule.
+    pub fn list_models(owner: &str, collection: Option<&str>) -> Result<Vec<FuelModel>, FuelError> {
+        let mut url = format!("https://fuel.gazebosim.org/1.0/{owner}/models");
+        if let Some(collection) = collection {
+            url = format!("https://fuel.gazebosim.org/1.0/{owner}/collections/{collection}/models");
+        }
+        reqwest::blocking::get(url)
+            .and_then(|response| response.json())
+            .map_err(|e| FuelError::Request(e.to_string()))
+    }
+
+    /// The on-disk cache root, handed to [`FuelClient::fetch`] so it can run
+    /// without borrowing `self`.
+    pub fn cache_dir(&self) -> PathBuf {
+        self.cache_dir.clone()
+    }
+
+    /// A model already resolved earlier this session, if any.
+    pub fn cached(&self, owner: &str, name: &str, version: u32) -> Option<AssetSource> {
+        self.resolved
+            .get(&(owner.to_string(), name.to_string(), version))
+            .cloned()
+    }
+
+    /// Remember a model resolved by a background [`FuelClient::fetch`] task
+    /// so that placing it again this session skips straight to the cache.
+    pub fn remember_resolved(&mut self, owner: &str, name: &str, version: u32, source: AssetSource) {
+        self.resolved
+            .insert((owner.to_string(), name.to_string(), version), source);
+    }
+
+    /// Resolve `owner/name@version` into a stable local [`AssetSource`],
+    /// downloading and caching the model the first time it is requested.
+    ///
+    /// Deliberately takes no `&self`/`&mut self`: this runs on a background
+    /// [`bevy::tasks::IoTaskPool`] task (see
+    /// `ObjectPlacement::place_fuel_model_3d`), and `reqwest`'s async client
+    /// has no Tokio reactor to poll on unless one happens to be driving the
+    /// task pool, which bevy's isn't. `reqwest::blocking` runs its own
+    /// single-use runtime internally, so it works correctly from a plain
+    /// worker thread without the caller needing to set anything up.
+    pub fn fetch(
+        owner: String,
+        name: String,
+        version: u32,
+        cache_dir: PathBuf,
+    ) -> Result<AssetSource, FuelError> {
+        let model_dir = cache_dir.join(&owner).join(&name).join(version.to_string());
+        if !model_dir.join("model.sdf").exists() {
+            Self::download(&owner, &name, version, &model_dir)?;
+        }
+        Ok(AssetSource::Local(model_dir.join("model.sdf")))
+    }
+
+    fn download(owner: &str, name: &str, version: u32, model_dir: &PathBuf) -> Result<(), FuelError> {
+        std::fs::create_dir_all(model_dir).map_err(|e| FuelError::Cache {
+            owner: owner.to_string(),
+            name: name.to_string(),
+            reason: e.to_string(),
+        })?;
+        let url = format!(
+            "https://fuel.gazebosim.org/1.0/{owner}/models/{name}/{version}/{name}.zip"
+        );
+        let bytes = reqwest::blocking::get(url)
+            .and_then(|response| response.bytes())
+            .map_err(|e| FuelError::Request(e.to_string()))?;
+        let reader = std::io::Cursor::new(bytes);
+        zip::ZipArchive::new(reader)
+            .and_then(|mut archive| archive.extract(model_dir))
+            .map_err(|e| FuelError::Cache {
+                owner: owner.to_string(),
+                name: name.to_string(),
+                reason: e.to_string(),
+            })
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use rmf_workcell_format::*;
+
+use crate::CurrentWorkspace;
+
+#[derive(Default)]
+pub struct WorkcellPropertiesWidgetPlugin {}
+
+impl Plugin for WorkcellPropertiesWidgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, show_workcell_properties_panel);
+    }
+}
+
+/// Inspector panel for editing the `WorkcellProperties` metadata block
+/// (license, maintainers, description, version, ROS package dependencies)
+/// that `export_package`/`export_sdf_package` read to populate
+/// `package.xml`/`model.config`. Without this, that metadata could only be
+/// set by hand-editing the saved workcell file, defeating the point of
+/// exporting a publishable package.
+fn show_workcell_properties_panel(
+    mut egui_context: EguiContexts,
+    current_workspace: Res<CurrentWorkspace>,
+    mut q_properties: Query<&mut WorkcellProperties>,
+) {
+    let Some(root) = current_workspace.root else {
+        return;
+    };
+    let Ok(mut properties) = q_properties.get_mut(root) else {
+        return;
+    };
+
+    egui::Window::new("Workcell Properties").show(egui_context.ctx_mut(), |ui| {
+        ui.horizontal(|ui| {
+            ui.label("License (SPDX):");
+            ui.text_edit_singleline(&mut properties.license);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Description:");
+            ui.text_edit_singleline(&mut properties.description);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Version:");
+            ui.text_edit_singleline(&mut properties.version);
+        });
+
+        ui.separator();
+        ui.label("Maintainers");
+        let mut remove_maintainer = None;
+        for (i, maintainer) in properties.maintainers.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut maintainer.name);
+                ui.text_edit_singleline(&mut maintainer.email);
+                if ui.button("x").clicked() {
+                    remove_maintainer = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove_maintainer {
+            properties.maintainers.remove(i);
+        }
+        if ui.button("+ Add maintainer").clicked() {
+            properties.maintainers.push(Maintainer {
+                name: String::new(),
+                email: String::new(),
+            });
+        }
+
+        ui.separator();
+        ui.label("ROS package dependencies");
+        let mut remove_dependency = None;
+        for (i, dependency) in properties.dependencies.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(dependency);
+                if ui.button("x").clicked() {
+                    remove_dependency = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove_dependency {
+            properties.dependencies.remove(i);
+        }
+        if ui.button("+ Add dependency").clicked() {
+            properties.dependencies.push(String::new());
+        }
+    });
+}
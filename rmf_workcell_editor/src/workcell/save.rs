@@ -17,9 +17,12 @@
 
 use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
 
+use crate::workcell::asset_path::{relativize_workcell_assets, RelativeUriStyle};
+use crate::workcell::sdf_package_exporter::{generate_sdf_package, SdfPackageContext};
+use crate::workcell::spdx::validate_spdx_license;
 use crate::workcell::urdf_package_exporter::{generate_package, PackageContext, Person};
 use crate::ExportFormat;
 use crate::{CollisionMeshMarker, VisualMeshMarker};
@@ -46,14 +49,21 @@ fn parent_in_workcell(q_parents: &Query<&Parent>, entity: Entity, root: Entity)
     AncestorIter::new(q_parents, entity).any(|p| p == root)
 }
 
+/// Tracks the next [`SiteID`] to hand out. Keeping this as a resource
+/// instead of deriving it from the current entity count each save means IDs
+/// that get freed up (e.g. by deleting a frame) are never reused while
+/// anything else might still be holding onto them, and saves stay
+/// diff-friendly since unrelated entities don't get renumbered just because
+/// something earlier in iteration order was added or removed.
+#[derive(Resource, Default, Clone, Copy)]
+struct NextSiteID(u32);
+
 // This is mostly duplicated with the function in site/save.rs, however this case
 // is a lot simpler, also site/save.rs checks for children of levels but there are no levels here
 fn assign_site_ids(world: &mut World, workcell: Entity) {
-    // TODO(luca) actually keep site IDs instead of always generating them from scratch
-    // (as it is done in site editor)
     let mut state: SystemState<(
         Query<
-            Entity,
+            (Entity, Option<&SiteID>),
             (
                 Or<(
                     With<FrameMarker>,
@@ -66,21 +76,46 @@ fn assign_site_ids(world: &mut World, workcell: Entity) {
             ),
         >,
         Query<&Children>,
+        Query<&SiteID>,
     )> = SystemState::new(world);
-    let (q_used_entities, q_children) = state.get(world);
+    let (q_used_entities, q_children, q_site_id) = state.get(world);
 
-    let mut new_entities = vec![workcell];
+    let mut entities = vec![workcell];
     for e in q_children.iter_descendants(workcell) {
         if q_used_entities.get(e).is_ok() {
-            new_entities.push(e);
+            entities.push(e);
         }
     }
 
-    for (idx, entity) in new_entities.iter().enumerate() {
-        world
-            .entity_mut(*entity)
-            .insert(SiteID(idx.try_into().unwrap()));
+    // Entities that already carry a SiteID (e.g. loaded from a file, or
+    // assigned on a previous save) keep it. The counter resource is
+    // advanced past every ID already in use so a freshly assigned ID never
+    // collides with one that external tooling might still be holding a
+    // reference to.
+    let mut used_ids: HashSet<u32> = entities
+        .iter()
+        .filter_map(|e| q_site_id.get(*e).ok())
+        .map(|id| id.0)
+        .collect();
+    let unassigned: Vec<Entity> = entities
+        .iter()
+        .copied()
+        .filter(|e| q_site_id.get(*e).is_err())
+        .collect();
+
+    let mut next_id = world.get_resource::<NextSiteID>().copied().unwrap_or_default().0;
+    if let Some(max_used) = used_ids.iter().max() {
+        next_id = next_id.max(max_used + 1);
     }
+    for entity in unassigned {
+        while used_ids.contains(&next_id) {
+            next_id += 1;
+        }
+        world.entity_mut(entity).insert(SiteID(next_id));
+        used_ids.insert(next_id);
+        next_id += 1;
+    }
+    world.insert_resource(NextSiteID(next_id));
 }
 
 pub fn generate_workcell(
@@ -303,28 +338,115 @@ pub fn save_workcell(world: &mut World) {
                     }
                 };
             }
+            ExportFormat::Sdf => {
+                match export_sdf_package(&path, workcell) {
+                    Ok(()) => {
+                        info!("Successfully exported SDF package");
+                    }
+                    Err(err) => {
+                        error!("Failed to export SDF package: {err}");
+                    }
+                };
+            }
         }
     }
 }
 
 fn export_package(
     output_directory: &PathBuf,
-    workcell: Workcell,
+    mut workcell: Workcell,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let properties = &workcell.properties;
+    validate_spdx_license(&properties.license)?;
+    let project_name = workcell.properties.name.0.clone() + "_description";
     let package_context = PackageContext {
-        license: "TODO".to_string(),
-        maintainers: vec![Person {
-            name: "TODO".to_string(),
-            email: "todo@todo.com".to_string(),
-        }],
-        project_name: workcell.properties.name.0.clone() + "_description",
+        license: properties.license.clone(),
+        maintainers: properties
+            .maintainers
+            .iter()
+            .map(|m| Person {
+                name: m.name.clone(),
+                email: m.email.clone(),
+            })
+            .collect(),
+        project_name: project_name.clone(),
         fixed_frame: "world".to_string(),
-        dependencies: vec![],
-        project_description: "TODO".to_string(),
-        project_version: "0.0.1".to_string(),
+        dependencies: properties.dependencies.clone(),
+        project_description: properties.description.clone(),
+        project_version: properties.version.clone(),
         urdf_file_name: "robot.urdf".to_string(),
     };
 
+    relativize_workcell_assets(
+        &mut workcell,
+        output_directory,
+        &project_name,
+        RelativeUriStyle::RosPackage,
+    )?;
     generate_package(workcell, package_context, output_directory)?;
     Ok(())
 }
+
+fn export_sdf_package(
+    output_directory: &PathBuf,
+    workcell: Workcell,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let properties = &workcell.properties;
+    validate_spdx_license(&properties.license)?;
+    let sdf_context = SdfPackageContext {
+        project_name: workcell.properties.name.0.clone() + "_description",
+        project_description: properties.description.clone(),
+        project_version: properties.version.clone(),
+        sdf_file_name: "model.sdf".to_string(),
+    };
+
+    generate_sdf_package(&workcell, sdf_context, output_directory)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_existing_site_ids_and_fills_gaps() {
+        let mut world = World::new();
+        let workcell = world.spawn_empty().id();
+        let frame_a = world
+            .spawn((FrameMarker, SiteID(5)))
+            .set_parent(workcell)
+            .id();
+        let frame_b = world.spawn(FrameMarker).set_parent(workcell).id();
+
+        assign_site_ids(&mut world, workcell);
+
+        assert_eq!(world.get::<SiteID>(frame_a).unwrap().0, 5);
+        let new_id = world.get::<SiteID>(frame_b).unwrap().0;
+        assert_ne!(new_id, 5);
+    }
+
+    #[test]
+    fn reassigning_does_not_change_ids_already_handed_out() {
+        let mut world = World::new();
+        let workcell = world.spawn_empty().id();
+        let frame = world.spawn(FrameMarker).set_parent(workcell).id();
+
+        assign_site_ids(&mut world, workcell);
+        let first_id = world.get::<SiteID>(frame).unwrap().0;
+        assign_site_ids(&mut world, workcell);
+
+        assert_eq!(world.get::<SiteID>(frame).unwrap().0, first_id);
+    }
+
+    #[test]
+    fn never_reuses_an_id_still_in_use_elsewhere() {
+        let mut world = World::new();
+        let workcell = world.spawn_empty().id();
+        world.spawn((FrameMarker, SiteID(0))).set_parent(workcell);
+        let frame_b = world.spawn(FrameMarker).set_parent(workcell).id();
+
+        assign_site_ids(&mut world, workcell);
+
+        assert_ne!(world.get::<SiteID>(frame_b).unwrap().0, 0);
+    }
+}
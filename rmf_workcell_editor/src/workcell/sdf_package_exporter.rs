@@ -0,0 +1,264 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use std::fs;
+use std::path::Path;
+
+use rmf_workcell_format::*;
+
+use crate::workcell::asset_path::{relativize_workcell_assets, RelativeUriStyle};
+
+/// Package metadata needed to write out an SDF model directory, mirroring
+/// `urdf_package_exporter::PackageContext` but for the `model.config`
+/// manifest rather than a `package.xml`.
+pub struct SdfPackageContext {
+    pub project_name: String,
+    pub project_description: String,
+    pub project_version: String,
+    pub sdf_file_name: String,
+}
+
+/// Write `workcell` out as an SDF model directory: `model.config` plus a
+/// `model.sdf` describing every frame as a `<link>`, every joint as a
+/// `<joint>`, and every visual/collision as geometry nested under the link
+/// it is parented to. Visuals and collisions parented directly to the
+/// workcell root rather than to a frame are not written. Reuses the same
+/// `output_directory` layout as the URDF exporter so the result sits next
+/// to a URDF export of the same workcell.
+pub fn generate_sdf_package(
+    workcell: &Workcell,
+    context: SdfPackageContext,
+    output_directory: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(output_directory)?;
+
+    let mut workcell = workcell.clone();
+    // Gazebo-style SDF loaders resolve <uri> directly against the
+    // directory the SDF file lives in (or GZ_SIM_RESOURCE_PATH), not ROS's
+    // package:// scheme, so this export gets its own relative style rather
+    // than reusing the URDF exporter's.
+    relativize_workcell_assets(
+        &mut workcell,
+        output_directory,
+        &context.project_name,
+        RelativeUriStyle::ModelRelative,
+    )?;
+
+    let sdf = generate_sdf(&workcell, &context);
+    fs::write(output_directory.join(&context.sdf_file_name), sdf)?;
+
+    let config = format!(
+        r#"<?xml version="1.0"?>
+<model>
+  <name>{name}</name>
+  <version>{version}</version>
+  <sdf version="1.9">{sdf_file}</sdf>
+  <description>{description}</description>
+</model>
+"#,
+        name = context.project_name,
+        version = context.project_version,
+        sdf_file = context.sdf_file_name,
+        description = context.project_description,
+    );
+    fs::write(output_directory.join("model.config"), config)?;
+
+    Ok(())
+}
+
+fn generate_sdf(workcell: &Workcell, context: &SdfPackageContext) -> String {
+    let mut links = String::new();
+    for (id, frame) in &workcell.frames {
+        links.push_str(&link_element(*id, frame, workcell));
+    }
+
+    let mut joints = String::new();
+    for (id, joint) in &workcell.joints {
+        joints.push_str(&joint_element(*id, joint, workcell));
+    }
+
+    format!(
+        r#"<?xml version="1.0"?>
+<sdf version="1.9">
+  <model name="{name}">
+{links}{joints}  </model>
+</sdf>
+"#,
+        name = context.project_name,
+        links = links,
+        joints = joints,
+    )
+}
+
+fn link_element(id: u32, frame: &Parented<u32, Frame>, workcell: &Workcell) -> String {
+    let link_name = link_name(id, &frame.bundle.name);
+    let mut body = String::new();
+
+    if let Some(inertia) = workcell
+        .inertias
+        .values()
+        .find(|i| i.parent == id)
+        .map(|i| &i.bundle)
+    {
+        body.push_str(&inertial_element(inertia));
+    }
+
+    for visual in workcell.visuals.values().filter(|v| v.parent == id) {
+        body.push_str(&geometry_element("visual", &visual.bundle));
+    }
+    for collision in workcell.collisions.values().filter(|c| c.parent == id) {
+        body.push_str(&geometry_element("collision", &collision.bundle));
+    }
+
+    format!(
+        "    <link name=\"{link_name}\">\n      <pose>{pose}</pose>\n{body}    </link>\n",
+        link_name = link_name,
+        pose = pose_to_sdf(&frame.bundle.anchor.translation_pose()),
+        body = body,
+    )
+}
+
+fn joint_element(id: u32, joint: &Parented<u32, Joint>, workcell: &Workcell) -> String {
+    let joint_type = match &joint.bundle.properties {
+        JointProperties::Fixed => "fixed",
+        JointProperties::Prismatic(_) => "prismatic",
+        JointProperties::Revolute(_) => "revolute",
+        JointProperties::Continuous(_) => "continuous",
+    };
+    let parent_name = workcell
+        .frames
+        .get(&joint.parent)
+        .map(|f| link_name(joint.parent, &f.bundle.name))
+        .unwrap_or_else(|| "world".to_string());
+    // The child of a joint is the frame that is parented to the joint's own
+    // SiteID, mirroring how `generate_workcell` threads parentage through
+    // frames/joints via SiteID rather than an explicit child reference.
+    let child_name = workcell
+        .frames
+        .iter()
+        .find(|(_, f)| f.parent == id)
+        .map(|(child_id, f)| link_name(*child_id, &f.bundle.name))
+        .unwrap_or_else(|| format!("joint_{id}_child"));
+    let axis = match &joint.bundle.properties {
+        JointProperties::Fixed => String::new(),
+        JointProperties::Prismatic(dof) | JointProperties::Revolute(dof) | JointProperties::Continuous(dof) => {
+            axis_element(dof)
+        }
+    };
+    format!(
+        "    <joint name=\"{name}\" type=\"{joint_type}\">\n      <parent>{parent}</parent>\n      <child>{child}</child>\n{axis}    </joint>\n",
+        name = joint.bundle.name.0,
+        joint_type = joint_type,
+        parent = parent_name,
+        child = child_name,
+        axis = axis,
+    )
+}
+
+fn axis_element(dof: &SingleDofJoint) -> String {
+    let limit = dof
+        .limits
+        .map(|limits| {
+            format!(
+                "<limit><lower>{lower}</lower><upper>{upper}</upper></limit>",
+                lower = limits[0],
+                upper = limits[1],
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "      <axis>\n        <xyz>{x} {y} {z}</xyz>\n        {limit}\n      </axis>\n",
+        x = dof.axis[0],
+        y = dof.axis[1],
+        z = dof.axis[2],
+        limit = limit,
+    )
+}
+
+fn inertial_element(inertia: &Inertia) -> String {
+    format!(
+        "      <inertial>\n        <pose>{pose}</pose>\n        <mass>{mass}</mass>\n        <inertia>\n          <ixx>{ixx}</ixx><ixy>{ixy}</ixy><ixz>{ixz}</ixz>\n          <iyy>{iyy}</iyy><iyz>{iyz}</iyz><izz>{izz}</izz>\n        </inertia>\n      </inertial>\n",
+        pose = pose_to_sdf(&inertia.center),
+        mass = inertia.mass.0,
+        ixx = inertia.moment.ixx,
+        ixy = inertia.moment.ixy,
+        ixz = inertia.moment.ixz,
+        iyy = inertia.moment.iyy,
+        iyz = inertia.moment.iyz,
+        izz = inertia.moment.izz,
+    )
+}
+
+fn geometry_element(tag: &str, model: &WorkcellModel) -> String {
+    let geometry = match &model.geometry {
+        Geometry::Mesh { source, scale } => {
+            let scale = scale.unwrap_or([1.0, 1.0, 1.0].into());
+            format!(
+                "<mesh><uri>{uri}</uri><scale>{x} {y} {z}</scale></mesh>",
+                uri = source.to_string(),
+                x = scale.x,
+                y = scale.y,
+                z = scale.z,
+            )
+        }
+        Geometry::Primitive(PrimitiveShape::Box { size }) => format!(
+            "<box><size>{x} {y} {z}</size></box>",
+            x = size[0],
+            y = size[1],
+            z = size[2],
+        ),
+        Geometry::Primitive(PrimitiveShape::Cylinder { radius, length }) => format!(
+            "<cylinder><radius>{radius}</radius><length>{length}</length></cylinder>",
+        ),
+        Geometry::Primitive(PrimitiveShape::Sphere { radius }) => {
+            format!("<sphere><radius>{radius}</radius></sphere>")
+        }
+        Geometry::Primitive(PrimitiveShape::Capsule { radius, length }) => format!(
+            "<capsule><radius>{radius}</radius><length>{length}</length></capsule>",
+        ),
+    };
+    format!(
+        "      <{tag} name=\"{name}\">\n        <pose>{pose}</pose>\n        <geometry>{geometry}</geometry>\n      </{tag}>\n",
+        tag = tag,
+        name = model.name,
+        pose = pose_to_sdf(&model.pose),
+        geometry = geometry,
+    )
+}
+
+fn link_name(id: u32, name: &NameInWorkcell) -> String {
+    if name.0.is_empty() {
+        format!("link_{id}")
+    } else {
+        name.0.clone()
+    }
+}
+
+/// Render a `Pose` in SDF's `x y z roll pitch yaw` convention.
+fn pose_to_sdf(pose: &Pose) -> String {
+    let p = pose.trans;
+    let (roll, pitch, yaw) = pose.rot.as_euler_extrinsic_xyz();
+    format!(
+        "{x} {y} {z} {roll} {pitch} {yaw}",
+        x = p[0],
+        y = p[1],
+        z = p[2],
+        roll = roll,
+        pitch = pitch,
+        yaw = yaw,
+    )
+}
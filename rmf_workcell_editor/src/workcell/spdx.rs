@@ -0,0 +1,32 @@
+/*
+ * Copyright (C) 2024 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use thiserror::Error as ThisError;
+
+#[derive(ThisError, Debug, Clone)]
+#[error("'{0}' is not a valid SPDX license expression")]
+pub struct InvalidSpdxLicense(pub String);
+
+/// Check that `license` parses as an SPDX license expression (e.g.
+/// `"Apache-2.0"` or `"MIT OR Apache-2.0"`), so a package exported from a
+/// `WorkcellProperties` with a typo'd license doesn't silently ship a
+/// `package.xml` that downstream tooling rejects.
+pub fn validate_spdx_license(license: &str) -> Result<(), InvalidSpdxLicense> {
+    spdx::Expression::parse(license)
+        .map(|_| ())
+        .map_err(|_| InvalidSpdxLicense(license.to_string()))
+}